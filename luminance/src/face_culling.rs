@@ -1,5 +1,11 @@
 //! Face culling is the operation of removing triangles if they’re facing the screen in a specific
 //! direction with a specific mode.
+//!
+//! Face culling is only one part of how a primitive gets rasterized, though. [`RasterizationState`]
+//! gathers it alongside the polygon fill mode and the depth-bias setting into the full
+//! rasterization state meant to be consumed by the render path. The render path itself lives
+//! outside this module and isn’t part of this tree; wiring `RasterizationState` into it — so that
+//! toggling wireframe or depth bias doesn’t need a separate ad-hoc API — is still outstanding.
 
 /// Face culling setup.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -63,3 +69,101 @@ pub enum FaceCullingMode {
   /// Always cull any triangle.
   Both,
 }
+
+/// Polygon fill mode.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FillMode {
+  /// Fill the whole polygon.
+  Fill,
+  /// Draw only the polygon edges (wireframe rendering).
+  Line,
+  /// Draw only the polygon vertices (point-cloud rendering).
+  Point,
+}
+
+impl Default for FillMode {
+  fn default() -> Self {
+    FillMode::Fill
+  }
+}
+
+/// Depth bias (a.k.a. polygon offset), used to push shadow-caster or decal geometry away from the
+/// depth buffer so that it doesn’t z-fight with the surface it’s drawn against.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DepthBias {
+  /// Constant depth offset.
+  pub(crate) constant_factor: f32,
+  /// Depth offset scaled by the polygon’s slope, steeper polygons get pushed further.
+  pub(crate) slope_scaled_factor: f32,
+}
+
+impl DepthBias {
+  /// Create a new [`DepthBias`].
+  pub fn new(constant_factor: f32, slope_scaled_factor: f32) -> Self {
+    DepthBias {
+      constant_factor,
+      slope_scaled_factor,
+    }
+  }
+
+  pub fn constant_factor(&self) -> f32 {
+    self.constant_factor
+  }
+
+  pub fn slope_scaled_factor(&self) -> f32 {
+    self.slope_scaled_factor
+  }
+}
+
+impl Default for DepthBias {
+  /// No depth bias.
+  fn default() -> Self {
+    DepthBias::new(0., 0.)
+  }
+}
+
+/// Full primitive rasterization state: face culling, polygon fill mode and depth bias, gathered
+/// into a single piece of state meant to be consumed by the render path.
+///
+/// Note: the render path that would actually bind this state to a draw call isn’t part of this
+/// module and doesn’t exist in this tree; this type isn’t wired into anything yet.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RasterizationState {
+  pub(crate) face_culling: FaceCulling,
+  pub(crate) fill_mode: FillMode,
+  pub(crate) depth_bias: Option<DepthBias>,
+}
+
+impl RasterizationState {
+  /// Create a new [`RasterizationState`].
+  pub fn new(
+    face_culling: FaceCulling,
+    fill_mode: FillMode,
+    depth_bias: Option<DepthBias>,
+  ) -> Self {
+    RasterizationState {
+      face_culling,
+      fill_mode,
+      depth_bias,
+    }
+  }
+
+  pub fn face_culling(&self) -> FaceCulling {
+    self.face_culling
+  }
+
+  pub fn fill_mode(&self) -> FillMode {
+    self.fill_mode
+  }
+
+  pub fn depth_bias(&self) -> Option<DepthBias> {
+    self.depth_bias
+  }
+}
+
+impl Default for RasterizationState {
+  /// `CCW` / `Back` face culling, `Fill` polygon mode, no depth bias.
+  fn default() -> Self {
+    RasterizationState::new(FaceCulling::default(), FillMode::default(), None)
+  }
+}
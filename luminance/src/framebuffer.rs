@@ -9,29 +9,54 @@
 //! # Framebuffers
 //!
 //! A framebuffer is an object maintaining the required GPU state to hold images you render to. It
-//! gathers two important concepts:
+//! gathers three important concepts:
 //!
 //!   - *Color buffers*.
 //!   - *Depth buffers*.
+//!   - *Stencil buffers*.
 //!
 //! The *color buffers* hold the color images you render to. A framebuffer can hold several of them
 //! with different color formats. The *depth buffers* hold the depth images you render to.
-//! Framebuffers can hold only one depth buffer.
+//! Framebuffers can hold only one depth buffer. The *stencil buffer* holds the per-pixel stencil
+//! values used by stencil-test effects (outlines, portals, shadow volumes, masked UI). Framebuffers
+//! can hold only one stencil buffer. `DepthSlot` and `StencilSlot` reify independent attachments,
+//! even when the same pixel format backs both, so a combined format (e.g. `Depth24Stencil8`) that
+//! must live in a single attachment goes through `DepthStencilSlot` instead, which is reified
+//! exactly once and handed out by `Framebuffer::depth_stencil_slot`.
 //!
 //! # Framebuffer slots
 //!
-//! A framebuffer slot contains either its color buffers or its depth buffer. Sometimes, you might
-//! find it handy to have no slot at all for a given type of buffer. In that case, we use `()`.
+//! A framebuffer slot contains either its color buffers, its depth buffer or its stencil buffer.
+//! Sometimes, you might find it handy to have no slot at all for a given type of buffer. In that
+//! case, we use `()`.
 //!
 //! The slots are a way to convert the different formats you use for your framebuffers’ buffers into
 //! their respective texture representation so that you can handle the corresponding texels.
 //!
-//! Color buffers are abstracted by `ColorSlot` and the depth buffer by `DepthSlot`.
+//! Color buffers are abstracted by `ColorSlot`, the depth buffer by `DepthSlot`, the stencil
+//! buffer by `StencilSlot`, and a combined depth-stencil attachment by `DepthStencilSlot`.
 
 use crate::context::GraphicsContext;
-use crate::pixel::{ColorPixel, DepthPixel, PixelFormat, RenderablePixel};
+use crate::pixel::{ColorPixel, DepthPixel, PixelFormat, RenderablePixel, StencilPixel};
 use crate::texture::{Dim2, Dimensionable, Layerable};
 
+/// Number of samples held by a multisampled (MSAA) attachment.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Samples {
+  /// No multisampling: one sample per pixel.
+  X1,
+  X2,
+  X4,
+  X8,
+}
+
+impl Default for Samples {
+  /// Defaults to `X1`, i.e. no multisampling.
+  fn default() -> Self {
+    Samples::X1
+  }
+}
+
 pub trait Framebuffer<C, L, D>: Sized
 where
   L: Layerable,
@@ -45,6 +70,19 @@ where
 
   type DepthSlot: DepthSlot<C, L, D, Self::Textures>;
 
+  type StencilSlot: StencilSlot<C, L, D, Self::Textures>;
+
+  /// Backed by `()` unless the depth and stencil attachments are a single combined format, in
+  /// which case this is reified once, backs both attachments and is read via `depth_stencil_slot`.
+  ///
+  /// **Invariant implementors must uphold themselves:** `DepthSlot` and `StencilSlot` each reify
+  /// independently, even when set to the very same `P: DepthPixel + StencilPixel`. Nothing in this
+  /// trait stops a `Framebuffer` impl from setting `DepthSlot = StencilSlot = P` and leaving this
+  /// at `()`, which silently reifies the combined format twice into two separate GPU attachments.
+  /// Whenever a combined pixel format is used, set `DepthSlot = StencilSlot = ()` and route that
+  /// format through `DepthStencilSlot` instead, so it is reified exactly once.
+  type DepthStencilSlot: DepthStencilSlot<C, L, D, Self::Textures>;
+
   type Err;
 
   /// Get the back buffer with the given dimension.
@@ -57,7 +95,12 @@ where
   ///
   /// You’re always handed at least the base level of the texture. If you require any *additional*
   /// levels, you can pass the number via the `mipmaps` parameter.
-  fn new(ctx: &mut C, size: D::Size, mipmaps: usize) -> Result<Self, Self::Err>;
+  ///
+  /// `samples` controls multisampling. With anything other than `Samples::X1`, the color (and
+  /// depth/stencil) attachments are allocated as multisampled buffers, and a paired single-sample
+  /// *resolve* texture is allocated per color slot; call `resolve` to blit the multisampled
+  /// buffers into their resolve textures before sampling them.
+  fn new(ctx: &mut C, size: D::Size, mipmaps: usize, samples: Samples) -> Result<Self, Self::Err>;
 
   /// Dimension of the framebuffer.
   fn dimension(&self) -> D::Size;
@@ -66,7 +109,38 @@ where
   fn color_slot(&self) -> &Self::ColorSlot;
 
   /// Access the underlying depth slot.
+  ///
+  /// If the depth attachment is combined with stencil (e.g. `Depth24Stencil8`), `Self::DepthSlot`
+  /// must be `()` here and the combined texture read from `depth_stencil_slot` instead — setting
+  /// this to the combined pixel format would reify it a second time, separately from stencil.
   fn depth_slot(&self) -> &Self::DepthSlot;
+
+  /// Access the underlying stencil slot.
+  ///
+  /// If the stencil attachment is combined with depth (e.g. `Depth24Stencil8`), `Self::StencilSlot`
+  /// must be `()` here and the combined texture read from `depth_stencil_slot` instead — setting
+  /// this to the combined pixel format would reify it a second time, separately from depth.
+  fn stencil_slot(&self) -> &Self::StencilSlot;
+
+  /// Access the combined depth-stencil texture when `Self::DepthStencilSlot` is backed by a
+  /// combined pixel format (e.g. `Depth24Stencil8`) instead of independent `depth_slot`/
+  /// `stencil_slot` attachments. This is the single texture reified once and shared by both.
+  fn depth_stencil_slot(
+    &self,
+  ) -> &<Self::DepthStencilSlot as DepthStencilSlot<C, L, D, Self::Textures>>::Texture;
+
+  /// Resolve the multisampled color attachments of this framebuffer into their paired
+  /// single-sample resolve textures (see `resolved_color_slot`). A no-op if the framebuffer isn’t
+  /// multisampled.
+  ///
+  /// This only resolves color; depth and stencil attachments have no resolve textures and are not
+  /// affected.
+  fn resolve(&self) -> Result<(), Self::Err>;
+
+  /// Access the resolved, single-sample color textures, ready to be sampled by a later pass.
+  fn resolved_color_slot(
+    &self,
+  ) -> &<Self::ColorSlot as ColorSlot<C, L, D, Self::Textures>>::ResolveTextures;
 }
 
 pub trait ColorSlot<C, L, D, I>
@@ -76,15 +150,28 @@ where
 {
   type ColorTextures;
 
+  /// The paired single-sample resolve textures, sampled after a call to `Framebuffer::resolve`.
+  /// Equal to `ColorTextures` when the slot is never allocated with multisampling.
+  type ResolveTextures;
+
   const COLOR_FORMATS: &'static [PixelFormat];
 
-  /// Reify a list of raw textures.
+  /// Reify a list of raw textures, multisampled according to `samples`.
   fn reify_textures(
     ctx: &mut C,
     size: D::Size,
     mipmaps: usize,
+    samples: Samples,
     textures: &mut I,
   ) -> Self::ColorTextures;
+
+  /// Reify the paired single-sample resolve textures.
+  fn reify_resolve_textures(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    textures: &mut I,
+  ) -> Self::ResolveTextures;
 }
 
 impl<C, L, D, I> ColorSlot<C, L, D, I> for ()
@@ -94,9 +181,15 @@ where
 {
   type ColorTextures = ();
 
+  type ResolveTextures = ();
+
   const COLOR_FORMATS: &'static [PixelFormat] = &[];
 
-  fn reify_textures(_: &mut C, _: D::Size, _: usize, _: &mut I) -> Self::ColorTextures {
+  fn reify_textures(_: &mut C, _: D::Size, _: usize, _: Samples, _: &mut I) -> Self::ColorTextures {
+    ()
+  }
+
+  fn reify_resolve_textures(_: &mut C, _: D::Size, _: usize, _: &mut I) -> Self::ResolveTextures {
     ()
   }
 }
@@ -110,15 +203,27 @@ where
 {
   type ColorTextures = <I as ReifyTexture<C, L, D, P>>::Texture;
 
+  type ResolveTextures = <I as ReifyTexture<C, L, D, P>>::Texture;
+
   const COLOR_FORMATS: &'static [PixelFormat] = &[Self::PIXEL_FORMAT];
 
   fn reify_textures(
     ctx: &mut C,
     size: D::Size,
     mipmaps: usize,
+    samples: Samples,
     state: &mut I,
   ) -> Self::ColorTextures {
-    I::reify_texture(ctx, size, mipmaps, state)
+    I::reify_texture(ctx, size, mipmaps, samples, state)
+  }
+
+  fn reify_resolve_textures(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    state: &mut I,
+  ) -> Self::ResolveTextures {
+    I::reify_texture(ctx, size, mipmaps, Samples::X1, state)
   }
 }
 
@@ -134,15 +239,27 @@ macro_rules! impl_color_slot_tuple {
       ),* {
       type ColorTextures = ($(<I as ReifyTexture<C, L, D, $pf>>::Texture),*);
 
+      type ResolveTextures = ($(<I as ReifyTexture<C, L, D, $pf>>::Texture),*);
+
       const COLOR_FORMATS: &'static [PixelFormat] = &[$($pf::PIXEL_FORMAT),*];
 
       fn reify_textures(
         ctx: &mut C,
         size: D::Size,
         mipmaps: usize,
+        samples: Samples,
         state: &mut I,
       ) -> Self::ColorTextures {
-        ( $( <I as ReifyTexture<C, L, D, $pf>>::reify_texture(ctx, size, mipmaps, state) ),* )
+        ( $( <I as ReifyTexture<C, L, D, $pf>>::reify_texture(ctx, size, mipmaps, samples, state) ),* )
+      }
+
+      fn reify_resolve_textures(
+        ctx: &mut C,
+        size: D::Size,
+        mipmaps: usize,
+        state: &mut I,
+      ) -> Self::ResolveTextures {
+        ( $( <I as ReifyTexture<C, L, D, $pf>>::reify_texture(ctx, size, mipmaps, Samples::X1, state) ),* )
       }
     }
   }
@@ -173,8 +290,13 @@ where
 
   const DEPTH_FORMAT: Option<PixelFormat>;
 
-  fn reify_texture(ctx: &mut C, size: D::Size, mipmaps: usize, state: &mut I)
-    -> Self::DepthTexture;
+  fn reify_texture(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    samples: Samples,
+    state: &mut I,
+  ) -> Self::DepthTexture;
 }
 
 impl<C, L, D, I> DepthSlot<C, L, D, I> for ()
@@ -186,7 +308,7 @@ where
 
   const DEPTH_FORMAT: Option<PixelFormat> = None;
 
-  fn reify_texture(_: &mut C, _: D::Size, _: usize, _: &mut I) -> Self::DepthTexture {
+  fn reify_texture(_: &mut C, _: D::Size, _: usize, _: Samples, _: &mut I) -> Self::DepthTexture {
     ()
   }
 }
@@ -206,9 +328,122 @@ where
     ctx: &mut C,
     size: D::Size,
     mipmaps: usize,
+    samples: Samples,
     state: &mut I,
   ) -> Self::DepthTexture {
-    I::reify_texture(ctx, size, mipmaps, state)
+    I::reify_texture(ctx, size, mipmaps, samples, state)
+  }
+}
+
+pub trait StencilSlot<C, L, D, I>
+where
+  L: Layerable,
+  D: Dimensionable,
+{
+  type StencilTexture;
+
+  const STENCIL_FORMAT: Option<PixelFormat>;
+
+  fn reify_texture(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    samples: Samples,
+    state: &mut I,
+  ) -> Self::StencilTexture;
+}
+
+impl<C, L, D, I> StencilSlot<C, L, D, I> for ()
+where
+  L: Layerable,
+  D: Dimensionable,
+{
+  type StencilTexture = ();
+
+  const STENCIL_FORMAT: Option<PixelFormat> = None;
+
+  fn reify_texture(_: &mut C, _: D::Size, _: usize, _: Samples, _: &mut I) -> Self::StencilTexture {
+    ()
+  }
+}
+
+impl<C, L, D, I, P> StencilSlot<C, L, D, I> for P
+where
+  L: Layerable,
+  D: Dimensionable,
+  I: ReifyTexture<C, L, D, Self>,
+  Self: StencilPixel,
+{
+  type StencilTexture = <I as ReifyTexture<C, L, D, Self>>::Texture;
+
+  const STENCIL_FORMAT: Option<PixelFormat> = Some(Self::PIXEL_FORMAT);
+
+  fn reify_texture(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    samples: Samples,
+    state: &mut I,
+  ) -> Self::StencilTexture {
+    I::reify_texture(ctx, size, mipmaps, samples, state)
+  }
+}
+
+/// A pixel format that can back both the depth and the stencil attachment of a `Framebuffer` from
+/// a single shared attachment (e.g. `Depth24Stencil8`). Unlike `DepthSlot`/`StencilSlot`, which
+/// reify their texture independently even when given the same pixel format, `DepthStencilSlot` is
+/// reified exactly once; `Framebuffer::depth_stencil_slot` hands out that one texture.
+pub trait DepthStencilSlot<C, L, D, I>
+where
+  L: Layerable,
+  D: Dimensionable,
+{
+  type Texture;
+
+  const FORMAT: Option<PixelFormat>;
+
+  fn reify_texture(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    samples: Samples,
+    state: &mut I,
+  ) -> Self::Texture;
+}
+
+impl<C, L, D, I> DepthStencilSlot<C, L, D, I> for ()
+where
+  L: Layerable,
+  D: Dimensionable,
+{
+  type Texture = ();
+
+  const FORMAT: Option<PixelFormat> = None;
+
+  fn reify_texture(_: &mut C, _: D::Size, _: usize, _: Samples, _: &mut I) -> Self::Texture {
+    ()
+  }
+}
+
+impl<C, L, D, I, P> DepthStencilSlot<C, L, D, I> for P
+where
+  L: Layerable,
+  D: Dimensionable,
+  I: ReifyTexture<C, L, D, P>,
+  Self: DepthPixel + StencilPixel,
+{
+  type Texture = <I as ReifyTexture<C, L, D, P>>::Texture;
+
+  const FORMAT: Option<PixelFormat> = Some(<Self as DepthPixel>::PIXEL_FORMAT);
+
+  fn reify_texture(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    samples: Samples,
+    state: &mut I,
+  ) -> Self::Texture {
+    I::reify_texture(ctx, size, mipmaps, samples, state)
   }
 }
 
@@ -219,5 +454,13 @@ where
 {
   type Texture;
 
-  fn reify_texture(ctx: &mut C, size: D::Size, mipmaps: usize, state: &mut Self) -> Self::Texture;
+  /// Reify a texture, allocated with the given sample count. Implementations must branch on
+  /// `samples`: anything other than `Samples::X1` allocates a multisampled texture.
+  fn reify_texture(
+    ctx: &mut C,
+    size: D::Size,
+    mipmaps: usize,
+    samples: Samples,
+    state: &mut Self,
+  ) -> Self::Texture;
 }
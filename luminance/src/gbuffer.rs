@@ -0,0 +1,118 @@
+//! Deferred-shading G-buffer preset.
+//!
+//! A G-buffer is a [`Framebuffer`](crate::framebuffer::Framebuffer) whose color slots hold the
+//! material attributes written by a geometry (prepass) pass: base color and metalness packed in
+//! one RGBA slot, a world-space normal packed into two channels in another, and emissive color
+//! with roughness in a third. A subsequent full-screen lighting pass samples all three textures,
+//! reconstructs the per-pixel material and shades once per screen pixel instead of once per
+//! fragment.
+//!
+//! Normals are packed with octahedral encoding (see [`encode_normal`] / [`decode_normal`]) so they
+//! survive an 8-bit-per-channel target.
+
+use crate::pixel::{RGBA8UI, RG16F};
+
+/// Preset color slot for a deferred-shading G-buffer.
+///
+///   - Slot 0: base color (RGB) + metallic (A).
+///   - Slot 1: octahedral-packed world-space normal (RG).
+///   - Slot 2: emissive color (RGB) + roughness (A).
+pub type GBufferColorSlot = (RGBA8UI, RG16F, RGBA8UI);
+
+/// The reified color textures of a [`GBufferColorSlot`], ready to be bound as sampler inputs to a
+/// lighting pass.
+///
+/// Each material attribute keeps its own texture type parameter because the three slots don’t all
+/// hold the same pixel format (`RGBA8UI`, `RG16F`, `RGBA8UI`) and so don’t all reify to the same
+/// texture type; see `ColorSlot::ColorTextures`.
+pub struct GBufferTextures<BaseColorMetallic, Normal, EmissiveRoughness> {
+  /// Base color (RGB) + metallic (A).
+  pub base_color_metallic: BaseColorMetallic,
+  /// Octahedral-packed world-space normal.
+  pub normal: Normal,
+  /// Emissive color (RGB) + roughness (A).
+  pub emissive_roughness: EmissiveRoughness,
+}
+
+/// Turn the reified `ColorTextures` of a G-buffer framebuffer into the named input texture set
+/// expected by a subsequent lighting pass.
+pub fn gbuffer_input_textures<BaseColorMetallic, Normal, EmissiveRoughness>(
+  textures: (BaseColorMetallic, Normal, EmissiveRoughness),
+) -> GBufferTextures<BaseColorMetallic, Normal, EmissiveRoughness> {
+  let (base_color_metallic, normal, emissive_roughness) = textures;
+
+  GBufferTextures {
+    base_color_metallic,
+    normal,
+    emissive_roughness,
+  }
+}
+
+/// Encode a unit-length world-space normal into two channels using octahedral encoding.
+///
+/// The output channels lie in `[-1; 1]` and are meant to be stored in a two-channel color slot
+/// (see [`GBufferColorSlot`]).
+pub fn encode_normal(n: [f32; 3]) -> [f32; 2] {
+  let [x, y, z] = n;
+  let l1_norm = x.abs() + y.abs() + z.abs();
+  let (u, v) = (x / l1_norm, y / l1_norm);
+
+  if z >= 0.0 {
+    [u, v]
+  } else {
+    [(1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum()]
+  }
+}
+
+/// Decode a normal previously packed with [`encode_normal`] back into a unit-length vector.
+pub fn decode_normal(e: [f32; 2]) -> [f32; 3] {
+  let [u, v] = e;
+  let z = 1.0 - u.abs() - v.abs();
+
+  let (x, y) = if z >= 0.0 {
+    (u, v)
+  } else {
+    ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum())
+  };
+
+  let len = (x * x + y * y + z * z).sqrt();
+  [x / len, y / len, z / len]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_roundtrips(n: [f32; 3]) {
+    let decoded = decode_normal(encode_normal(n));
+
+    for i in 0..3 {
+      assert!(
+        (decoded[i] - n[i]).abs() < 1e-5,
+        "expected {:?}, got {:?}",
+        n,
+        decoded
+      );
+    }
+  }
+
+  #[test]
+  fn roundtrip_axis_aligned() {
+    assert_roundtrips([1., 0., 0.]);
+    assert_roundtrips([0., 1., 0.]);
+    assert_roundtrips([0., 0., 1.]);
+    assert_roundtrips([0., 0., -1.]);
+  }
+
+  #[test]
+  fn roundtrip_diagonal() {
+    let s = 1. / 3f32.sqrt();
+    assert_roundtrips([s, s, s]);
+  }
+
+  #[test]
+  fn roundtrip_diagonal_negative_z() {
+    let s = 1. / 3f32.sqrt();
+    assert_roundtrips([s, s, -s]);
+  }
+}
@@ -9,8 +9,8 @@ pub trait HasBuffer {
   /// pointer, a method to retrieve data, a handle, whatever.
   type ABuffer;
 
-  /// Create a new buffer with a given size.
-  fn new(size: usize) -> Self::ABuffer;
+  /// Create a new buffer with a given size and usage hint.
+  fn new(size: usize, usage: BufferUsage) -> Self::ABuffer;
   /// Write values into the buffer.
   fn write_whole<T>(buffer: &Self::ABuffer, values: &Vec<T>);
   /// Write a single value in the buffer at a given offset.
@@ -20,6 +20,12 @@ pub trait HasBuffer {
   /// `Err(BufferError::Overflow)` if you provide an offset that doesn’t lie in the GPU allocated
   /// region.
   fn write<T>(buffer: &Self::ABuffer, x: T, offset: usize) -> Result<(), BufferError>;
+  /// Write a contiguous range of values into the buffer, starting at a given byte offset.
+  ///
+  /// # Failures
+  ///
+  /// `Err(BufferError::TooManyValues)` if the range doesn’t lie in the GPU allocated region.
+  fn write_range<T>(buffer: &Self::ABuffer, offset: usize, values: &[T]) -> Result<(), BufferError>;
   /// Read all values from the buffer.
   fn read_whole<T>(buffer: &Self::ABuffer) -> Vec<T>;
   /// Read a single value from the buffer at a given offset.
@@ -28,6 +34,11 @@ pub trait HasBuffer {
   ///
   /// `None` if you provide an offset that doesn’t lie in the GPU allocated region.
   fn read<T>(buffer: &Self::ABuffer, offset: usize) -> Option<&T>;
+  /// Map the whole buffer so that it can be written to directly, without going through
+  /// `write`/`write_whole`. The GPU region backing the buffer is handed out as a plain slice.
+  fn map<T>(buffer: &mut Self::ABuffer) -> &mut [T];
+  /// Unmap a buffer previously mapped with `map`.
+  fn unmap(buffer: &mut Self::ABuffer);
 }
 
 /// Buffer errors.
@@ -37,6 +48,20 @@ pub enum BufferError {
   , TooManyValues
 }
 
+/// Hint given to the backend about how a `Buffer` will be used, so that it can pick the most
+/// appropriate GPU allocation.
+///
+///   - `Static`: the buffer is uploaded once and read many times by the GPU.
+///   - `Dynamic`: the buffer is updated occasionally and read many times by the GPU.
+///   - `Stream`: the buffer is updated on (almost) every frame, such as streamed vertex or uniform
+///     data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BufferUsage {
+    Static
+  , Dynamic
+  , Stream
+}
+
 /// A `Buffer` is a GPU region you can picture as an array. It has a static size and cannot be
 /// resized. The size is expressed in number of elements lying in the buffer, not in bytes.
 #[derive(Debug)]
@@ -48,15 +73,50 @@ pub struct Buffer<C: HasBuffer, A, T> {
 }
 
 impl<C: HasBuffer, A, T> Buffer<C, A, T> {
-  pub fn new(_: A, size: u32) -> Buffer<C, A, T> {
+  pub fn new(a: A, size: u32) -> Buffer<C, A, T> {
+    Self::new_with_usage(a, size, BufferUsage::Static)
+  }
+
+  pub fn new_with_usage(_: A, size: u32, usage: BufferUsage) -> Buffer<C, A, T> {
     let size = size as usize;
-    let buffer = C::new(size * mem::size_of::<T>());
+    let buffer = C::new(size * mem::size_of::<T>(), usage);
     Buffer { repr: buffer, size: size, _a: PhantomData, _t: PhantomData }
   }
 
   pub fn get(&self, i: u32) -> Option<&T> {
     C::read(&self.repr, i as usize * mem::size_of::<T>())
   }
+
+  /// Access the backend-specific handle backing this buffer, e.g. to bind it to a pipeline.
+  pub(crate) fn as_raw(&self) -> &C::ABuffer {
+    &self.repr
+  }
+
+  /// Write a contiguous range of values into the buffer, starting at element offset `offset`.
+  ///
+  /// # Failures
+  ///
+  /// `Err(BufferError::TooManyValues)` if `offset + values.len()` doesn’t lie in the buffer.
+  pub fn write_range(&self, offset: u32, values: &[T]) -> Result<(), BufferError> {
+    let offset = offset as usize;
+
+    if offset + values.len() > self.size {
+      return Err(BufferError::TooManyValues);
+    }
+
+    C::write_range(&self.repr, offset * mem::size_of::<T>(), values)
+  }
+
+  /// Map the whole buffer and hand out a mutable slice backed directly by the GPU region, for
+  /// zero-copy fills. Call `unmap` once done writing to it.
+  pub fn as_slice_mut(&mut self) -> &mut [T] {
+    C::map(&mut self.repr)
+  }
+
+  /// Unmap a buffer previously mapped with `as_slice_mut`.
+  pub fn unmap(&mut self) {
+    C::unmap(&mut self.repr)
+  }
 }
 
 impl<C: HasBuffer, A, T> Buffer<C, A, T> where T: Clone {
@@ -72,4 +132,74 @@ impl<C: HasBuffer, A, T> Index<u32> for Buffer<C, A, T> {
   fn index(&self, i: u32) -> &T {
 		self.get(i).unwrap()
   }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::cell::RefCell;
+
+  struct FakeBuffer;
+
+  impl HasBuffer for FakeBuffer {
+    type ABuffer = RefCell<Vec<u8>>;
+
+    fn new(size: usize, _: BufferUsage) -> Self::ABuffer {
+      RefCell::new(vec![0; size])
+    }
+
+    fn write_whole<T>(_: &Self::ABuffer, _: &Vec<T>) {
+      unimplemented!()
+    }
+
+    fn write<T>(_: &Self::ABuffer, _: T, _: usize) -> Result<(), BufferError> {
+      unimplemented!()
+    }
+
+    fn write_range<T>(
+      buffer: &Self::ABuffer,
+      offset: usize,
+      values: &[T],
+    ) -> Result<(), BufferError> {
+      let bytes = buffer.borrow();
+
+      if offset + values.len() * mem::size_of::<T>() > bytes.len() {
+        return Err(BufferError::TooManyValues);
+      }
+
+      Ok(())
+    }
+
+    fn read_whole<T>(_: &Self::ABuffer) -> Vec<T> {
+      unimplemented!()
+    }
+
+    fn read<T>(_: &Self::ABuffer, _: usize) -> Option<&T> {
+      unimplemented!()
+    }
+
+    fn map<T>(_: &mut Self::ABuffer) -> &mut [T] {
+      unimplemented!()
+    }
+
+    fn unmap(_: &mut Self::ABuffer) {
+      unimplemented!()
+    }
+  }
+
+  #[test]
+  fn write_range_in_bounds() {
+    let buffer: Buffer<FakeBuffer, (), u32> = Buffer::new((), 4);
+    assert!(buffer.write_range(0, &[1u32, 2, 3]).is_ok());
+  }
+
+  #[test]
+  fn write_range_overflow() {
+    let buffer: Buffer<FakeBuffer, (), u32> = Buffer::new((), 4);
+
+    match buffer.write_range(2, &[1u32, 2, 3]) {
+      Err(BufferError::TooManyValues) => {}
+      other => panic!("expected TooManyValues, got {:?}", other),
+    }
+  }
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+use crate::buffer::{Buffer, HasBuffer};
+
+/// Marker type tagging a `Buffer` as bound to a compute shader as a shader storage buffer (SSBO),
+/// readable and writable from the GPU side.
+pub struct ComputeBuffer;
+
+/// A `Buffer` usable as a shader storage buffer by a `ComputePipeline`.
+pub type Ssbo<C, T> = Buffer<C, ComputeBuffer, T>;
+
+/// Implement this trait to provide compute pipelines.
+pub trait HasComputePipeline: HasBuffer {
+  /// A type representing minimal information to operate on a compute pipeline: the linked compute
+  /// shader program handle.
+  type AComputePipeline;
+
+  /// A compute shader, ready to be linked into a compute pipeline.
+  type ComputeShader;
+
+  /// Create a new compute pipeline from a compute shader.
+  fn new_compute_pipeline(shader: &Self::ComputeShader) -> Self::AComputePipeline;
+
+  /// Bind a buffer to a shader storage buffer binding point, so that the next dispatch can read
+  /// and write it.
+  fn bind_buffer(pipeline: &Self::AComputePipeline, binding: u32, buffer: &Self::ABuffer);
+
+  /// Dispatch the compute pipeline over a 3D grid of work-groups.
+  fn dispatch(pipeline: &Self::AComputePipeline, x: u32, y: u32, z: u32);
+
+  /// Insert a memory barrier so that CPU reads issued after this call observe the GPU writes of
+  /// every dispatch issued before it.
+  fn memory_barrier();
+}
+
+/// A compute pipeline, pairing a compute shader with the dispatch and read-back machinery needed
+/// to run it.
+pub struct ComputePipeline<C: HasComputePipeline> {
+  repr: C::AComputePipeline,
+}
+
+impl<C: HasComputePipeline> ComputePipeline<C> {
+  /// Build a new compute pipeline from a compute shader.
+  pub fn new(shader: &C::ComputeShader) -> Self {
+    ComputePipeline { repr: C::new_compute_pipeline(shader) }
+  }
+
+  /// Bind an `Ssbo` to a shader storage buffer binding point ahead of a `dispatch`.
+  pub fn bind_buffer<T>(&self, binding: u32, buffer: &Ssbo<C, T>) {
+    C::bind_buffer(&self.repr, binding, buffer.as_raw());
+  }
+
+  /// Dispatch the compute pipeline over a 3D grid of work-groups.
+  ///
+  /// This issues a memory barrier right after dispatching so that a subsequent `read`/`read_whole`
+  /// (see `HasBuffer`) on any `Ssbo` bound to the pipeline observes the GPU writes.
+  pub fn dispatch(&self, x: u32, y: u32, z: u32) {
+    C::dispatch(&self.repr, x, y, z);
+    C::memory_barrier();
+  }
+}